@@ -51,37 +51,57 @@ pub fn branch() -> String {
     )
 }
 
-/// Emit Git revision and branch information for the build script.
+/// Returns the commit date of `HEAD`, in the same format as
+/// `metadata::build_date`, so `commit-date:` reflects the commit named by
+/// `BUILD_GIT_REVISION` rather than when the binary happened to be built.
+pub fn commit_date() -> String {
+    gix::open(".")
+        .ok()
+        .and_then(|repo| {
+            let mut head = repo.head().ok()?;
+            let commit = head.peel_to_commit_in_place().ok()?;
+            let time = commit.time().ok()?;
+            chrono::DateTime::from_timestamp(time.seconds, 0)
+                .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Emit Git revision, branch, and commit-date information for the build script.
 pub fn emit_git_info() {
     let revision = revision();
     println!("cargo:rustc-env=BUILD_GIT_REVISION={revision}");
 
     let branch = branch();
     println!("cargo:rustc-env=BUILD_GIT_BRANCH={branch}");
+
+    let commit_date = commit_date();
+    println!("cargo:rustc-env=BUILD_GIT_DATE={commit_date}");
 }
 
-/// Extract package version from the latest tag.
-#[allow(dead_code)]
-pub fn tag() -> Result<(), Box<dyn std::error::Error>> {
-    let cargo_pkg_version = env!("CARGO_PKG_VERSION").to_string();
-    let r = gix::discover(std::path::Path::new("."))?;
-    let mut h = r.head().unwrap();
-    let c = h.peel_to_commit_in_place().unwrap();
-    let names = gix::commit::describe::SelectRef::AllTags;
-    let t = c
+/// Describe the nearest reachable tag, e.g. `0.1.0-5-gabc1234-dirty`.
+///
+/// Falls back to `CARGO_PKG_VERSION` when there is no repository, no HEAD, or
+/// no reachable tag, rather than panicking.
+pub fn describe_version() -> String {
+    describe_version_inner().unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string())
+}
+
+fn describe_version_inner() -> Option<String> {
+    let repo = gix::discover(".").ok()?;
+    let mut head = repo.head().ok()?;
+    let commit = head.peel_to_commit_in_place().ok()?;
+
+    let mut format = commit
         .describe()
-        .names(names)
+        .names(gix::commit::describe::SelectRef::AllTags)
         .id_as_fallback(false)
         .format()
-        .map(|mut fmt| {
-            if fmt.depth > 0 {
-                fmt.dirty_suffix = Some("dirty".to_string());
-            }
-            fmt.depth = 0;
-            fmt.long = false;
-            fmt.to_string()
-        })
-        .unwrap_or(cargo_pkg_version);
-    println!("cargo:rustc-env=VERSION={t}");
-    Ok(())
+        .ok()?;
+
+    if repo.is_dirty().unwrap_or(false) {
+        format.dirty_suffix = Some("dirty".to_string());
+    }
+
+    Some(format.to_string())
 }
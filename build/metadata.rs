@@ -2,14 +2,31 @@ use std::env;
 use std::process::Command;
 
 /// Returns the build date in ISO 8601 format.
+///
+/// Honors `SOURCE_DATE_EPOCH` (Unix seconds) when present, so the same
+/// inputs produce the same output across rebuilds; falls back to the wall
+/// clock otherwise.
 pub fn build_date() -> String {
-    chrono::Utc::now()
-        .format("%Y-%m-%d %H:%M:%S UTC")
-        .to_string()
+    let instant = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse::<i64>().ok())
+        .and_then(|epoch| chrono::DateTime::from_timestamp(epoch, 0))
+        .unwrap_or_else(chrono::Utc::now);
+    instant.format("%Y-%m-%d %H:%M:%S UTC").to_string()
 }
 
 /// Returns the user running the build.
+///
+/// `BUILD_USER` always wins. Otherwise, when `SOURCE_DATE_EPOCH` is set
+/// (signalling a reproducible build), falls back to `"unknown"` instead of
+/// probing the system for an identity that would vary between builders.
 pub fn build_user() -> String {
+    if let Ok(user) = env::var("BUILD_USER") {
+        return user;
+    }
+    if env::var("SOURCE_DATE_EPOCH").is_ok() {
+        return "unknown".to_string();
+    }
     env::var("USER")
         .or_else(|_| env::var("USERNAME"))
         .unwrap_or_else(|_| {
@@ -44,6 +61,8 @@ fn rust_version() -> String {
 
 /// Emit build metadata information (date and user).
 pub fn emit_build_metadata() {
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    println!("cargo:rerun-if-env-changed=BUILD_USER");
     let build_date = build_date();
     let build_user = build_user();
     println!("cargo:rustc-env=BUILD_DATE={build_date}");
@@ -51,13 +70,16 @@ pub fn emit_build_metadata() {
 }
 
 /// Detect the compiler and emit related environment variables and cfg flags.
-pub fn detect_compiler() {
+///
+/// Returns whether the compiler meets the MSRV floor, so callers can gate
+/// their own cfgs on the same check instead of re-deriving it.
+pub fn detect_compiler() -> bool {
     let version = rust_version();
     println!("cargo:rustc-env=TOOLCHAIN={version}");
     nightly();
     beta();
     stable();
-    msrv();
+    msrv()
 }
 
 #[rustversion::nightly]
@@ -82,8 +104,11 @@ fn stable() {
 fn stable() {}
 
 #[rustversion::since(1.67)]
-fn msrv() {
+fn msrv() -> bool {
     println!("cargo:rustc-cfg=msrv");
+    true
 }
 #[rustversion::before(1.67)]
-fn msrv() {}
+fn msrv() -> bool {
+    false
+}
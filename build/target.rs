@@ -0,0 +1,146 @@
+use std::env;
+use std::process::Command;
+
+/// Libc flavor a target triple links against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Libc {
+    Musl,
+    Glibc,
+    Other,
+}
+
+/// What we know about static linking for a given target triple.
+#[derive(Debug, Clone, Copy)]
+pub struct TargetInfo {
+    pub libc: Libc,
+    /// Whether this target produces a static binary without any extra flags.
+    pub static_by_default: bool,
+    /// System libs to link statically when static linking is requested.
+    pub static_libs: &'static [&'static str],
+}
+
+/// Known libc flavors for Linux targets, matched by substring against the
+/// target triple (the exact vendor/ABI components don't change how we link).
+/// Gated on `linux` too, since `gnu`/`musl` alone also show up in non-Linux
+/// triples (e.g. `x86_64-pc-windows-gnu`) that need a completely different
+/// static-lib set.
+const KNOWN_LINUX_TARGETS: &[(&str, TargetInfo)] = &[
+    (
+        "musl",
+        TargetInfo {
+            libc: Libc::Musl,
+            static_by_default: true,
+            static_libs: &[],
+        },
+    ),
+    (
+        "gnu",
+        TargetInfo {
+            libc: Libc::Glibc,
+            static_by_default: false,
+            static_libs: &["ssl", "crypto", "z", "pthread", "dl", "rt"],
+        },
+    ),
+];
+
+/// Resolve linking info for a target triple, falling back to an `Other` row
+/// for anything we don't recognize (e.g. bionic, msvc, windows-gnu, wasm).
+pub fn resolve(target: &str) -> TargetInfo {
+    if !target.contains("linux") {
+        return TargetInfo {
+            libc: Libc::Other,
+            static_by_default: false,
+            static_libs: &[],
+        };
+    }
+    KNOWN_LINUX_TARGETS
+        .iter()
+        .find(|(marker, _)| target.contains(marker))
+        .map_or(
+            TargetInfo {
+                libc: Libc::Other,
+                static_by_default: false,
+                static_libs: &[],
+            },
+            |(_, info)| *info,
+        )
+}
+
+/// Emit `cargo:rustc-link-lib`/`rustc-link-arg` decisions for `target`.
+///
+/// Purely informational unless `TEMPLATE_RS_STATIC=1` is set, in which case
+/// the static system libs for the target's libc flavor are actually linked;
+/// for a libc with no static-linking recipe the build fails instead.
+/// `host` is cargo's own `HOST` (the triple `rustc` runs on), used to tell a
+/// native build from a cross build so the musl suggestion only fires when it
+/// would apply to the binary actually being produced. `msrv_ok` is
+/// `metadata::detect_compiler`'s msrv check, used to gate the
+/// `unsupported_static_target` cfg below the same toolchain floor as the
+/// rest of the build script's cfgs.
+pub fn configure(target: &str, host: &str, msrv_ok: bool) {
+    println!("cargo:rerun-if-env-changed=TEMPLATE_RS_STATIC");
+    let info = resolve(target);
+    let static_requested = env::var("TEMPLATE_RS_STATIC").as_deref() == Ok("1");
+
+    match info.libc {
+        Libc::Musl => {
+            println!(
+                "cargo:warning=Building for musl target: {target}. Expect a fully static binary."
+            );
+        }
+        Libc::Glibc => {
+            println!("cargo:warning=Detected non-musl (glibc) target: {target}.");
+            if static_requested {
+                println!(
+                    "cargo:warning=TEMPLATE_RS_STATIC=1: statically linking {} for glibc. This is fragile and not recommended for production.",
+                    info.static_libs.join(", ")
+                );
+                link_static(&info);
+            } else if !info.static_by_default {
+                println!("cargo:warning=Fully static linking with glibc may be problematic.");
+                if target == host {
+                    suggest_musl();
+                }
+            }
+        }
+        Libc::Other => {
+            // We don't have a static-linking recipe for this libc at all, so
+            // there's nothing safe to link even when asked: fail the build
+            // instead of emitting a bare `-static` that's meaningless (or
+            // actively wrong, e.g. on macOS/MSVC) for the linker in use.
+            println!("cargo:warning=Unrecognized libc for target: {target}. Static linking is not modeled for this target.");
+            if msrv_ok {
+                println!("cargo:rustc-cfg=unsupported_static_target");
+            }
+            if static_requested {
+                panic!(
+                    "TEMPLATE_RS_STATIC=1 was requested for target `{target}`, but this libc has no static-linking recipe. Unset TEMPLATE_RS_STATIC or build for a target with a known libc (musl/gnu on Linux)."
+                );
+            }
+        }
+    }
+}
+
+fn link_static(info: &TargetInfo) {
+    println!("cargo:rustc-link-arg=-static");
+    for lib in info.static_libs {
+        println!("cargo:rustc-link-lib=static={lib}");
+    }
+}
+
+fn suggest_musl() {
+    let musl_target = "x86_64-unknown-linux-musl";
+    let Ok(output) = Command::new("rustc").args(["--print", "target-list"]).output() else {
+        return;
+    };
+    let target_list = String::from_utf8_lossy(&output.stdout);
+    if target_list.contains(musl_target) {
+        println!(
+            "cargo:warning=Consider using musl for a reliably static binary:\n  cargo build --target={musl_target}"
+        );
+    } else {
+        println!(
+            "cargo:warning=If you need a fully static binary, install the musl target:\n  rustup target add {musl_target}\nThen build with:\n  cargo build --target={musl_target}"
+        );
+    }
+}
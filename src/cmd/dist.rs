@@ -0,0 +1,205 @@
+//! `dist` subcommand: package the compiled binary into a release archive set.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use miette::{IntoDiagnostic, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::version::{current_host_triple, VersionInfo};
+
+const CRATE_NAME: &str = env!("CARGO_PKG_NAME");
+
+/// One packaged file and its integrity metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Artifact {
+    pub name: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// A deterministic description of everything `dist` produced for a release.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: String,
+    pub git_revision: String,
+    pub git_branch: String,
+    pub toolchain: String,
+    pub build_date: String,
+    pub target: String,
+    pub artifacts: Vec<Artifact>,
+}
+
+/// Run the `dist` subcommand: build a tarball + manifest for `bin` under `out_dir`.
+pub fn run(
+    bin: Option<PathBuf>,
+    out_dir: PathBuf,
+    target: Option<String>,
+    sign: bool,
+    key: Option<PathBuf>,
+) -> Result<()> {
+    let bin = match bin {
+        Some(bin) => bin,
+        None => std::env::current_exe().into_diagnostic()?,
+    };
+    let info = VersionInfo::current();
+    let target = target.unwrap_or_else(current_host_triple);
+
+    fs::create_dir_all(&out_dir).into_diagnostic()?;
+
+    let archive_name = format!("{CRATE_NAME}-{}-{target}.tar.gz", info.version);
+    let archive_path = out_dir.join(&archive_name);
+    write_tarball(&bin, &archive_path)?;
+
+    let artifact = Artifact {
+        name: archive_name,
+        size: fs::metadata(&archive_path).into_diagnostic()?.len(),
+        sha256: sha256_file(&archive_path)?,
+    };
+
+    let manifest = Manifest {
+        version: info.version.to_string(),
+        git_revision: info.commit_hash.to_string(),
+        git_branch: info.branch.to_string(),
+        toolchain: info.toolchain.to_string(),
+        build_date: info.build_date.to_string(),
+        target,
+        artifacts: vec![artifact],
+    };
+
+    write_manifest(&manifest, &out_dir)?;
+
+    if sign {
+        super::sign::sign_artifacts(&manifest, &out_dir, key.as_deref())?;
+    }
+
+    println!("wrote {}", archive_path.display());
+    Ok(())
+}
+
+/// Gzip-compress `bin` into a tarball at `archive_path` with a single deterministic entry.
+fn write_tarball(bin: &Path, archive_path: &Path) -> Result<()> {
+    let file = fs::File::create(archive_path).into_diagnostic()?;
+    let encoder = GzEncoder::new(file, Compression::best());
+    let mut builder = tar::Builder::new(encoder);
+    builder.mode(tar::HeaderMode::Deterministic);
+
+    let file_name = bin
+        .file_name()
+        .map_or_else(|| CRATE_NAME.to_string(), |n| n.to_string_lossy().to_string());
+    builder
+        .append_path_with_name(bin, file_name)
+        .into_diagnostic()?;
+
+    let encoder = builder.into_inner().into_diagnostic()?;
+    encoder.finish().into_diagnostic()?;
+    Ok(())
+}
+
+/// Hash a file's contents with SHA-256, returning the lowercase hex digest.
+pub(crate) fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path).into_diagnostic()?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher).into_diagnostic()?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Write `manifest.toml` and `manifest.json`, both with a stable field and artifact order.
+fn write_manifest(manifest: &Manifest, out_dir: &Path) -> Result<()> {
+    let mut manifest = manifest.clone();
+    manifest.artifacts.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let toml = toml::to_string_pretty(&manifest).into_diagnostic()?;
+    fs::write(out_dir.join("manifest.toml"), toml).into_diagnostic()?;
+
+    let json = serde_json::to_string_pretty(&manifest).into_diagnostic()?;
+    fs::write(out_dir.join("manifest.json"), json).into_diagnostic()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::scratch_dir;
+
+    #[test]
+    fn run_is_deterministic_across_out_dirs() {
+        let bin_dir = scratch_dir("dist-bin");
+        let bin_path = bin_dir.join("template-rs");
+        fs::write(&bin_path, b"pretend this is a compiled binary").unwrap();
+
+        let out_a = scratch_dir("dist-out-a");
+        let out_b = scratch_dir("dist-out-b");
+
+        run(
+            Some(bin_path.clone()),
+            out_a.clone(),
+            Some("x86_64-unknown-linux-gnu".to_string()),
+            false,
+            None,
+        )
+        .unwrap();
+        run(
+            Some(bin_path),
+            out_b.clone(),
+            Some("x86_64-unknown-linux-gnu".to_string()),
+            false,
+            None,
+        )
+        .unwrap();
+
+        let manifest_a = fs::read(out_a.join("manifest.json")).unwrap();
+        let manifest_b = fs::read(out_b.join("manifest.json")).unwrap();
+        assert_eq!(manifest_a, manifest_b, "manifest.json must be byte-identical");
+
+        let manifest: Manifest = serde_json::from_slice(&manifest_a).unwrap();
+        assert_eq!(manifest.artifacts.len(), 1);
+        let archive_name = &manifest.artifacts[0].name;
+
+        let archive_a = fs::read(out_a.join(archive_name)).unwrap();
+        let archive_b = fs::read(out_b.join(archive_name)).unwrap();
+        assert_eq!(archive_a, archive_b, "tarball must be byte-identical");
+
+        fs::remove_dir_all(&out_a).unwrap();
+        fs::remove_dir_all(&out_b).unwrap();
+    }
+
+    #[test]
+    fn write_manifest_sorts_artifacts_for_stable_output() {
+        let out_dir = scratch_dir("dist-manifest-sort");
+        let manifest = Manifest {
+            version: "0.1.0".to_string(),
+            git_revision: "abc1234".to_string(),
+            git_branch: "main".to_string(),
+            toolchain: "1.80.0 x86_64-unknown-linux-gnu (stable channel)".to_string(),
+            build_date: "2024-05-01 00:00:00 UTC".to_string(),
+            target: "x86_64-unknown-linux-gnu".to_string(),
+            artifacts: vec![
+                Artifact {
+                    name: "z.tar.gz".to_string(),
+                    size: 1,
+                    sha256: "0".repeat(64),
+                },
+                Artifact {
+                    name: "a.tar.gz".to_string(),
+                    size: 2,
+                    sha256: "1".repeat(64),
+                },
+            ],
+        };
+
+        write_manifest(&manifest, &out_dir).unwrap();
+
+        let written: Manifest =
+            serde_json::from_slice(&fs::read(out_dir.join("manifest.json")).unwrap()).unwrap();
+        assert_eq!(written.artifacts[0].name, "a.tar.gz");
+        assert_eq!(written.artifacts[1].name, "z.tar.gz");
+
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+}
@@ -0,0 +1,76 @@
+//! CLI subcommands for `template-rs`.
+
+use std::path::PathBuf;
+
+use clap::Subcommand;
+use miette::Result;
+
+mod dist;
+mod sign;
+#[cfg(test)]
+mod test_support;
+mod version;
+
+pub use version::VersionInfo;
+
+/// Top-level subcommands available on the `template-rs` CLI.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Print build-time provenance (git revision, branch, toolchain, ...).
+    Version {
+        /// Emit the version information as JSON instead of human-readable text.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Package the compiled binary into a release archive with a signed-ready manifest.
+    Dist {
+        /// Path to the binary to package (defaults to the current executable).
+        #[arg(long)]
+        bin: Option<PathBuf>,
+        /// Output directory for the produced artifacts.
+        #[arg(long, default_value = "dist")]
+        out_dir: PathBuf,
+        /// Target triple the binary was built for (defaults to the host triple).
+        #[arg(long)]
+        target: Option<String>,
+        /// Sign each artifact, writing a `.sha256` and `.sig` sidecar next to it.
+        #[arg(long)]
+        sign: bool,
+        /// Path to the ed25519 signing key (defaults to `TEMPLATE_RS_SIGN_KEY`).
+        #[arg(long)]
+        key: Option<PathBuf>,
+    },
+    /// Re-hash and verify a `dist` manifest's artifacts against their detached signatures.
+    Verify {
+        /// Path to the `manifest.json` (or `manifest.toml`) produced by `dist`.
+        manifest: PathBuf,
+        /// Path to the ed25519 public key used to verify signatures.
+        #[arg(long)]
+        public_key: PathBuf,
+    },
+}
+
+impl Command {
+    /// Dispatch to the handler for this subcommand.
+    pub async fn run(self) -> Result<()> {
+        match self {
+            Command::Version { json } => version::run(json),
+            Command::Dist {
+                bin,
+                out_dir,
+                target,
+                sign,
+                key,
+            } => dist::run(bin, out_dir, target, sign, key),
+            Command::Verify {
+                manifest,
+                public_key,
+            } => sign::verify(manifest, public_key),
+        }
+    }
+}
+
+/// Rustc-style long version string, for use as clap's `long_version`.
+pub fn long_version() -> String {
+    VersionInfo::current().long_string()
+}
@@ -0,0 +1,291 @@
+//! Artifact integrity signing and verification for `dist` output.
+//!
+//! Keys are raw 32-byte ed25519 values, hex-encoded one line per file: a seed
+//! for signing, the corresponding public key for verification.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use miette::{Diagnostic, IntoDiagnostic, Result};
+use thiserror::Error;
+
+use super::dist::{sha256_file, Artifact, Manifest};
+
+/// Env var carrying the path to the ed25519 signing key, when `--key` isn't passed.
+pub const SIGN_KEY_ENV: &str = "TEMPLATE_RS_SIGN_KEY";
+
+/// One artifact's verification failure, rendered as a `miette` diagnostic.
+#[derive(Debug, Error, Diagnostic)]
+pub enum SignError {
+    #[error("could not read artifact `{file}`")]
+    #[diagnostic(code(template_rs::dist::unreadable))]
+    Unreadable { file: String },
+
+    #[error("sha-256 mismatch for `{file}`")]
+    #[diagnostic(
+        code(template_rs::dist::hash_mismatch),
+        help("the artifact may have been corrupted or tampered with in transit")
+    )]
+    HashMismatch { file: String },
+
+    #[error("missing detached signature for `{file}`")]
+    #[diagnostic(
+        code(template_rs::dist::missing_signature),
+        help("expected a `{file}.sig` sidecar next to the artifact")
+    )]
+    MissingSignature { file: String },
+
+    #[error("malformed detached signature for `{file}`")]
+    #[diagnostic(code(template_rs::dist::malformed_signature))]
+    MalformedSignature { file: String },
+
+    #[error("signature verification failed for `{file}`")]
+    #[diagnostic(
+        code(template_rs::dist::bad_signature),
+        help("the public key may not match the signer, or the artifact was modified after signing")
+    )]
+    BadSignature { file: String },
+}
+
+/// The artifacts in a manifest that failed verification, reported together as
+/// one diagnostic with a related entry per failing file.
+#[derive(Debug, Error, Diagnostic)]
+#[error("{} artifact(s) failed verification", .failures.len())]
+#[diagnostic(code(template_rs::dist::verification_failed))]
+pub struct VerificationFailed {
+    #[related]
+    pub failures: Vec<SignError>,
+}
+
+/// Sign every artifact in `manifest`, writing a `.sha256` and `.sig` sidecar next to each.
+pub fn sign_artifacts(manifest: &Manifest, out_dir: &Path, key_path: Option<&Path>) -> Result<()> {
+    let signing_key = load_signing_key(key_path)?;
+
+    for artifact in &manifest.artifacts {
+        let artifact_path = out_dir.join(&artifact.name);
+
+        let digest = sha256_file(&artifact_path)?;
+        fs::write(
+            sidecar_path(&artifact_path, "sha256"),
+            format!("{digest}  {}\n", artifact.name),
+        )
+        .into_diagnostic()?;
+
+        let bytes = fs::read(&artifact_path).into_diagnostic()?;
+        let signature = signing_key.sign(&bytes);
+        fs::write(
+            sidecar_path(&artifact_path, "sig"),
+            hex::encode(signature.to_bytes()),
+        )
+        .into_diagnostic()?;
+    }
+
+    Ok(())
+}
+
+/// Run the `verify` subcommand: re-hash and check signatures for every artifact
+/// in `manifest_path`, returning one diagnostic per failing file.
+pub fn verify(manifest_path: PathBuf, public_key_path: PathBuf) -> Result<()> {
+    let manifest = load_manifest(&manifest_path)?;
+    let out_dir = manifest_path
+        .parent()
+        .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+    let public_key = load_verifying_key(&public_key_path)?;
+
+    let failures: Vec<SignError> = manifest
+        .artifacts
+        .iter()
+        .filter_map(|artifact| verify_one(&out_dir, artifact, &public_key).err())
+        .collect();
+
+    if failures.is_empty() {
+        println!("all {} artifact(s) verified", manifest.artifacts.len());
+        Ok(())
+    } else {
+        Err(VerificationFailed { failures }.into())
+    }
+}
+
+fn verify_one(
+    out_dir: &Path,
+    artifact: &Artifact,
+    public_key: &VerifyingKey,
+) -> std::result::Result<(), SignError> {
+    let artifact_path = out_dir.join(&artifact.name);
+
+    let digest = sha256_file(&artifact_path).map_err(|_| SignError::Unreadable {
+        file: artifact.name.clone(),
+    })?;
+    if digest != artifact.sha256 {
+        return Err(SignError::HashMismatch {
+            file: artifact.name.clone(),
+        });
+    }
+
+    let sig_hex = fs::read_to_string(sidecar_path(&artifact_path, "sig")).map_err(|_| {
+        SignError::MissingSignature {
+            file: artifact.name.clone(),
+        }
+    })?;
+    let sig_bytes = hex::decode(sig_hex.trim()).map_err(|_| SignError::MalformedSignature {
+        file: artifact.name.clone(),
+    })?;
+    let signature =
+        Signature::from_slice(&sig_bytes).map_err(|_| SignError::MalformedSignature {
+            file: artifact.name.clone(),
+        })?;
+
+    let bytes = fs::read(&artifact_path).map_err(|_| SignError::Unreadable {
+        file: artifact.name.clone(),
+    })?;
+    public_key
+        .verify(&bytes, &signature)
+        .map_err(|_| SignError::BadSignature {
+            file: artifact.name.clone(),
+        })
+}
+
+fn sidecar_path(artifact_path: &Path, extension: &str) -> PathBuf {
+    let mut name = artifact_path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+fn load_manifest(path: &Path) -> Result<Manifest> {
+    let contents = fs::read_to_string(path).into_diagnostic()?;
+    if path.extension().and_then(|ext| ext.to_str()) == Some("toml") {
+        toml::from_str(&contents).into_diagnostic()
+    } else {
+        serde_json::from_str(&contents).into_diagnostic()
+    }
+}
+
+fn load_signing_key(key_path: Option<&Path>) -> Result<SigningKey> {
+    let seed = load_hex_key(&resolve_key_path(key_path)?)?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+fn load_verifying_key(key_path: &Path) -> Result<VerifyingKey> {
+    let bytes = load_hex_key(key_path)?;
+    VerifyingKey::from_bytes(&bytes).into_diagnostic()
+}
+
+fn resolve_key_path(key_path: Option<&Path>) -> Result<PathBuf> {
+    if let Some(path) = key_path {
+        return Ok(path.to_path_buf());
+    }
+    std::env::var(SIGN_KEY_ENV)
+        .map(PathBuf::from)
+        .map_err(|_| miette::miette!("no signing key: pass --key or set {SIGN_KEY_ENV}"))
+}
+
+fn load_hex_key(path: &Path) -> Result<[u8; 32]> {
+    let contents = fs::read_to_string(path).into_diagnostic()?;
+    let bytes = hex::decode(contents.trim()).into_diagnostic()?;
+    bytes
+        .try_into()
+        .map_err(|_| miette::miette!("key at {} must be 32 bytes (64 hex chars)", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::scratch_dir;
+
+    /// A fixed (non-secret, test-only) ed25519 seed, so tests don't need an RNG dependency.
+    fn test_signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    /// Write a hex-encoded key to `path`, the format `load_hex_key` expects.
+    fn write_hex_key(path: &Path, bytes: &[u8]) {
+        fs::write(path, hex::encode(bytes)).unwrap();
+    }
+
+    /// Set up a dist directory with one artifact and a manifest describing it.
+    fn setup_artifact(out_dir: &Path) -> Manifest {
+        let artifact_path = out_dir.join("template-rs-0.1.0-x86_64-unknown-linux-gnu.tar.gz");
+        fs::write(&artifact_path, b"pretend this is a release tarball").unwrap();
+
+        Manifest {
+            version: "0.1.0".to_string(),
+            git_revision: "abc1234".to_string(),
+            git_branch: "main".to_string(),
+            toolchain: "1.80.0 x86_64-unknown-linux-gnu (stable channel)".to_string(),
+            build_date: "2024-05-01 00:00:00 UTC".to_string(),
+            target: "x86_64-unknown-linux-gnu".to_string(),
+            artifacts: vec![Artifact {
+                name: artifact_path.file_name().unwrap().to_string_lossy().to_string(),
+                size: fs::metadata(&artifact_path).unwrap().len(),
+                sha256: sha256_file(&artifact_path).unwrap(),
+            }],
+        }
+    }
+
+    #[test]
+    fn sign_then_verify_round_trips() {
+        let out_dir = scratch_dir("sign-round-trip");
+        let manifest = setup_artifact(&out_dir);
+
+        let signing_key = test_signing_key();
+        let key_path = out_dir.join("signing.key");
+        write_hex_key(&key_path, &signing_key.to_bytes());
+
+        sign_artifacts(&manifest, &out_dir, Some(&key_path)).unwrap();
+
+        let manifest_path = out_dir.join("manifest.json");
+        fs::write(&manifest_path, serde_json::to_string_pretty(&manifest).unwrap()).unwrap();
+
+        let public_key_path = out_dir.join("public.key");
+        write_hex_key(&public_key_path, &signing_key.verifying_key().to_bytes());
+
+        verify(manifest_path, public_key_path).unwrap();
+
+        fs::remove_dir_all(&out_dir).unwrap();
+    }
+
+    #[test]
+    fn verify_rejects_tampered_artifact() {
+        let out_dir = scratch_dir("sign-tampered");
+        let manifest = setup_artifact(&out_dir);
+
+        let signing_key = test_signing_key();
+        let key_path = out_dir.join("signing.key");
+        write_hex_key(&key_path, &signing_key.to_bytes());
+        sign_artifacts(&manifest, &out_dir, Some(&key_path)).unwrap();
+
+        fs::write(out_dir.join(&manifest.artifacts[0].name), b"tampered contents").unwrap();
+
+        let public_key = signing_key.verifying_key();
+        let err = verify_one(&out_dir, &manifest.artifacts[0], &public_key).unwrap_err();
+        assert!(matches!(err, SignError::HashMismatch { .. }));
+    }
+
+    #[test]
+    fn verify_rejects_missing_signature() {
+        let out_dir = scratch_dir("sign-missing-sig");
+        let manifest = setup_artifact(&out_dir);
+
+        let signing_key = test_signing_key();
+        let public_key = signing_key.verifying_key();
+        let err = verify_one(&out_dir, &manifest.artifacts[0], &public_key).unwrap_err();
+        assert!(matches!(err, SignError::MissingSignature { .. }));
+    }
+
+    #[test]
+    fn verify_rejects_wrong_public_key() {
+        let out_dir = scratch_dir("sign-wrong-key");
+        let manifest = setup_artifact(&out_dir);
+
+        let signing_key = test_signing_key();
+        let key_path = out_dir.join("signing.key");
+        write_hex_key(&key_path, &signing_key.to_bytes());
+        sign_artifacts(&manifest, &out_dir, Some(&key_path)).unwrap();
+
+        let wrong_key = SigningKey::from_bytes(&[9u8; 32]);
+        let err = verify_one(&out_dir, &manifest.artifacts[0], &wrong_key.verifying_key()).unwrap_err();
+        assert!(matches!(err, SignError::BadSignature { .. }));
+    }
+}
@@ -0,0 +1,12 @@
+//! Shared test fixtures for `cmd` submodule tests.
+
+use std::fs;
+use std::path::PathBuf;
+
+/// A fresh scratch directory under the system temp dir, unique per test.
+pub(crate) fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("template-rs-test-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
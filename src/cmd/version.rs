@@ -0,0 +1,104 @@
+//! `version` subcommand: build-time provenance in a rustc-style format.
+
+use miette::Result;
+use serde::Serialize;
+
+/// Build-time provenance, pulled from the `rustc-env` variables `build.rs` emits.
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionInfo {
+    pub version: &'static str,
+    pub commit_hash: &'static str,
+    pub commit_date: &'static str,
+    pub branch: &'static str,
+    pub host: String,
+    pub build_date: &'static str,
+    pub build_user: &'static str,
+    pub toolchain: &'static str,
+    pub channel: String,
+}
+
+impl VersionInfo {
+    /// Collect provenance from the environment variables baked in at build time.
+    pub fn current() -> Self {
+        let toolchain = env!("TOOLCHAIN");
+        let build_date = env!("BUILD_DATE");
+        Self {
+            version: env!("VERSION"),
+            commit_hash: env!("BUILD_GIT_REVISION"),
+            commit_date: env!("BUILD_GIT_DATE")
+                .split_whitespace()
+                .next()
+                .unwrap_or("unknown"),
+            branch: env!("BUILD_GIT_BRANCH"),
+            host: host_triple(toolchain),
+            build_date,
+            build_user: env!("BUILD_USER"),
+            toolchain,
+            channel: channel_word(toolchain),
+        }
+    }
+
+    fn short_commit(&self) -> &str {
+        self.commit_hash.get(0..7).unwrap_or(self.commit_hash)
+    }
+
+    /// Multi-line `rustc --version --verbose`-style summary.
+    pub fn long_string(&self) -> String {
+        format!(
+            "template-rs {version} ({short_commit} {commit_date}, {channel} channel)\n\
+             binary: template-rs\n\
+             commit-hash: {commit_hash}\n\
+             commit-date: {commit_date}\n\
+             branch: {branch}\n\
+             host: {host}\n\
+             build-date: {build_date}\n\
+             build-user: {build_user}\n\
+             toolchain: {toolchain}",
+            version = self.version,
+            short_commit = self.short_commit(),
+            commit_date = self.commit_date,
+            channel = self.channel,
+            commit_hash = self.commit_hash,
+            branch = self.branch,
+            host = self.host,
+            build_date = self.build_date,
+            build_user = self.build_user,
+            toolchain = self.toolchain,
+        )
+    }
+}
+
+/// Pull the host triple out of a `TOOLCHAIN` string (`"<semver> <host> (<channel> channel)"`).
+fn host_triple(toolchain: &str) -> String {
+    toolchain
+        .split_once(' ')
+        .and_then(|(_, rest)| rest.split_once(" ("))
+        .map_or_else(|| "unknown".to_string(), |(host, _)| host.to_string())
+}
+
+/// The host triple of the toolchain that built this binary, for commands that
+/// need a default `target` (e.g. `dist`).
+pub(crate) fn current_host_triple() -> String {
+    host_triple(env!("TOOLCHAIN"))
+}
+
+/// Pull the lowercase channel name out of a `TOOLCHAIN` string.
+fn channel_word(toolchain: &str) -> String {
+    toolchain
+        .rsplit_once('(')
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+        .map_or_else(|| "unknown".to_string(), str::to_lowercase)
+}
+
+/// Run the `version` subcommand.
+pub fn run(json: bool) -> Result<()> {
+    let info = VersionInfo::current();
+    if json {
+        let rendered =
+            serde_json::to_string_pretty(&info).map_err(|e| miette::miette!("{e}"))?;
+        println!("{rendered}");
+    } else {
+        println!("{}", info.long_string());
+    }
+    Ok(())
+}
@@ -29,7 +29,7 @@ impl From<LogLevel> for Level {
 
 #[derive(Parser, Debug)]
 #[command(name = "template-rs")]
-#[command(version, about, long_about = None)]
+#[command(version, long_version = cmd::long_version(), about, long_about = None)]
 pub struct Cli {
     /// Set the logging level
     #[arg(long, value_enum, default_value_t = LogLevel::Info)]
@@ -59,10 +59,7 @@ async fn main() -> Result<()> {
     info!("Starting template-rs");
 
     match cli.command {
-        Some(_cmd) => {
-            // Command handling would go here
-            info!("Command handling not yet implemented");
-        }
+        Some(cmd) => cmd.run().await?,
         None => {
             info!("No command specified");
         }
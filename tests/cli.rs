@@ -45,3 +45,40 @@ fn test_cli_no_args() {
 
     assert!(output.status.success(), "CLI with no args should succeed");
 }
+
+#[test]
+fn test_version_subcommand() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "version"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "version subcommand should succeed");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("commit-hash:"),
+        "version output should include commit-hash"
+    );
+    assert!(
+        stdout.contains("toolchain:"),
+        "version output should include toolchain"
+    );
+}
+
+#[test]
+fn test_version_subcommand_json() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "version", "--json"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "version --json subcommand should succeed"
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("\"commit_hash\""),
+        "JSON version output should include commit_hash field"
+    );
+}